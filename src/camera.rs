@@ -1,10 +1,99 @@
 use {
+    crate::film::{Film, Filter},
     crate::geometry::{Geometry, World},
     crate::image::Painter,
     crate::prelude::*,
-    std::path::Path,
+    std::{
+        path::Path,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc, Arc,
+        },
+        thread,
+    },
 };
 
+/// Tiles keep each worker's writes disjoint and cache-friendly.
+const TILE_SIZE: usize = 32;
+
+/// A tile's own RNG stream, seeded purely from `(base_seed, tile_index)` —
+/// `tile_index` is the tile's fixed position in [`Tile::grid`]'s output, not
+/// the order in which the shared tile counter happens to hand it to a
+/// thread — so a render is bit-for-bit reproducible for a given seed no
+/// matter how tiles are scheduled across workers.
+#[derive(Debug, Clone)]
+struct TileRng(u64);
+
+impl TileRng {
+    fn new(base_seed: u64, tile_index: usize) -> Self {
+        // splitmix64-style mixing so adjacent tile indices don't start from
+        // near-identical xorshift states
+        let mut z = base_seed ^ (tile_index as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        Self((z ^ (z >> 31)) | 1)
+    }
+
+    // xorshift64*
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn range(&mut self, range: std::ops::Range<f64>) -> f64 {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        range.start + unit * (range.end - range.start)
+    }
+
+    // Box-Muller, matching the shape of `Random::normal` closely enough for
+    // per-tile shutter-time jitter
+    fn normal(&mut self) -> f64 {
+        let u1 = self.range(f64::EPSILON..1.0);
+        let u2 = self.range(0.0..1.0);
+        (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+    }
+
+    // Fisher-Yates, in place
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn shuffle(&mut self, items: &mut [usize]) {
+        for i in (1..items.len()).rev() {
+            let j = self.range(0.0..(i as f64 + 1.0)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Tile {
+    x: usize,
+    y: usize,
+    w: usize,
+    h: usize,
+}
+
+impl Tile {
+    fn grid(width: usize, height: usize) -> Vec<Self> {
+        let mut tiles = Vec::new();
+        let mut y = 0;
+        while y < height {
+            let mut x = 0;
+            let h = TILE_SIZE.min(height - y);
+            while x < width {
+                let w = TILE_SIZE.min(width - x);
+                tiles.push(Self { x, y, w, h });
+                x += TILE_SIZE;
+            }
+            y += TILE_SIZE;
+        }
+        tiles
+    }
+}
+
 #[derive(Debug)]
 pub struct Camera {
     origin: Point3,
@@ -51,12 +140,34 @@ impl Camera {
 
     #[must_use]
     pub fn ray(&self, u: f64, v: f64) -> Ray {
-        let rd = self.aperture / 2.0 * Vec3::random_unit_disk();
+        self.ray_with_lens(u, v, Vec3::random_unit_disk(), Random::normal())
+    }
+
+    /// Like [`Camera::ray`], but takes an already-stratified lens sample
+    /// `(lens_u, lens_v)` in `[0, 1)^2` instead of drawing one by rejection.
+    #[must_use]
+    pub(crate) fn ray_stratified(&self, u: f64, v: f64, lens_u: f64, lens_v: f64) -> Ray {
+        self.ray_with_lens(u, v, Vec3::unit_disk_from_square(lens_u, lens_v), Random::normal())
+    }
+
+    /// Like [`Camera::ray_stratified`], but also takes an explicit shutter
+    /// time jitter instead of drawing it from the ambient `Random`, so a
+    /// caller driving its own RNG stream (e.g. a threaded tile renderer) can
+    /// make every random input to the ray reproducible.
+    #[must_use]
+    pub(crate) fn ray_seeded(
+        &self, u: f64, v: f64, lens_u: f64, lens_v: f64, time_jitter: f64,
+    ) -> Ray {
+        self.ray_with_lens(u, v, Vec3::unit_disk_from_square(lens_u, lens_v), time_jitter)
+    }
+
+    fn ray_with_lens(&self, u: f64, v: f64, lens: Vec3, time_jitter: f64) -> Ray {
+        let rd = self.aperture / 2.0 * lens;
         let offset = &self.horizontal_unit * rd.x + &self.vertical_unit * rd.y;
         let origin = &self.origin + offset;
         let direction = &self.lb + u * &self.horizontal_full + v * &self.vertical_full - &origin;
 
-        Ray::new(origin, direction, self.shutter_speed * Random::normal())
+        Ray::new(origin, direction, self.shutter_speed * time_jitter)
     }
 
     #[must_use]
@@ -65,13 +176,41 @@ impl Camera {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct TakePhotoSettings<'c, 'w> {
     camera: &'c Camera,
     world: &'w World,
     depth: usize,
     samples: usize,
     picture_height: usize,
+    threads: usize,
+    progress: Option<fn(done: usize, total: usize)>,
+    filter: Filter,
+    stratified: bool,
+    seed: u64,
+    // per-sample camera override driving true camera-motion blur, keyed by
+    // the same shutter-time jitter used for scene motion blur; see
+    // `CameraAnimation::render`. `dyn Fn` can't derive `Debug`, hence the
+    // manual impl below.
+    motion: Option<Arc<dyn Fn(f64) -> Camera + Send + Sync>>,
+}
+
+impl std::fmt::Debug for TakePhotoSettings<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TakePhotoSettings")
+            .field("camera", self.camera)
+            .field("world", self.world)
+            .field("depth", &self.depth)
+            .field("samples", &self.samples)
+            .field("picture_height", &self.picture_height)
+            .field("threads", &self.threads)
+            .field("progress", &self.progress)
+            .field("filter", &self.filter)
+            .field("stratified", &self.stratified)
+            .field("seed", &self.seed)
+            .field("motion", &self.motion.is_some())
+            .finish()
+    }
 }
 
 impl<'c, 'w> TakePhotoSettings<'c, 'w> {
@@ -83,6 +222,12 @@ impl<'c, 'w> TakePhotoSettings<'c, 'w> {
             depth: 8,
             samples: 50,
             picture_height: 108,
+            threads: 0,
+            progress: None,
+            filter: Filter::Box,
+            stratified: false,
+            seed: 0,
+            motion: None,
         }
     }
 
@@ -104,6 +249,60 @@ impl<'c, 'w> TakePhotoSettings<'c, 'w> {
         self
     }
 
+    /// Number of worker threads to render with. `0` (the default) auto-detects
+    /// the available parallelism and uses all cores.
+    #[must_use]
+    pub const fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Install a callback invoked on the main thread after each tile finishes,
+    /// receiving the number of tiles completed so far and the total tile count.
+    #[must_use]
+    pub const fn progress(mut self, progress: fn(done: usize, total: usize)) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Reconstruction filter used to splat samples into the final image.
+    /// Defaults to `Filter::Box`, i.e. the old box-averaging behavior.
+    #[must_use]
+    pub const fn filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Replace per-pixel pure-random sampling with a jittered `n`×`n` grid
+    /// (`n` the nearest integer to `sqrt(samples)`), also used to stratify
+    /// the depth-of-field lens samples, for more even coverage at equal
+    /// sample budgets.
+    #[must_use]
+    pub const fn stratified(mut self, stratified: bool) -> Self {
+        self.stratified = stratified;
+        self
+    }
+
+    /// Base seed each tile's own RNG stream is derived from, so a render is
+    /// reproducible regardless of which worker thread ends up rendering
+    /// which tile.
+    #[must_use]
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Override the camera used for each sample's ray with one rebuilt from
+    /// `time_jitter` (the same per-sample shutter-time jitter already used
+    /// for scene motion blur), instead of the fixed `camera` passed to
+    /// [`Camera::take_photo`]. [`CameraAnimation::render`] uses this so a
+    /// fast camera move blurs the way a fast-moving object already does.
+    #[must_use]
+    pub(crate) fn motion(mut self, motion: Arc<dyn Fn(f64) -> Camera + Send + Sync>) -> Self {
+        self.motion = Some(motion);
+        self
+    }
+
     fn background(ray: &Ray) -> Color {
         let unit = ray.direction.unit();
         let t = 0.5 * (unit.y + 1.0);
@@ -125,6 +324,90 @@ impl<'c, 'w> TakePhotoSettings<'c, 'w> {
         Self::background(ray)
     }
 
+    // color of one jittered sample taken at continuous position (px, py) of
+    // a `width`x`height` image, where pixel x spans [x, x + 1), with an
+    // explicit (already-stratified or not) lens sample and shutter-time
+    // jitter so the whole sample is reproducible from the caller's own RNG
+    #[allow(clippy::cast_precision_loss, clippy::too_many_arguments)]
+    fn sample(
+        &self, px: f64, py: f64, width: usize, height: usize, lens_u: f64, lens_v: f64,
+        time_jitter: f64,
+    ) -> Vec3 {
+        let u = px / width as f64;
+        let v = (height as f64 - py) / height as f64;
+        let ray = self.motion.as_ref().map_or_else(
+            || self.camera.ray_seeded(u, v, lens_u, lens_v, time_jitter),
+            |motion| motion(time_jitter).ray_seeded(u, v, lens_u, lens_v, time_jitter),
+        );
+        Self::ray_color(&ray, self.world, self.depth).into()
+    }
+
+    // renders `tile`, padded by the filter radius so splats can cross tile
+    // boundaries, returning the padded origin and a film covering it. All
+    // randomness comes from a `TileRng` seeded from `(self.seed, tile_index)`
+    // alone, so the result only depends on the tile's position in the fixed
+    // grid, never on which worker thread happened to render it.
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn render_tile(&self, tile: Tile, tile_index: usize, width: usize, height: usize) -> (usize, usize, Film) {
+        let pad = self.filter.radius().ceil() as usize;
+        let x0 = tile.x.saturating_sub(pad);
+        let y0 = tile.y.saturating_sub(pad);
+        let x1 = (tile.x + tile.w + pad).min(width);
+        let y1 = (tile.y + tile.h + pad).min(height);
+        let mut film = Film::new(x1 - x0, y1 - y0, self.filter);
+        let mut rng = TileRng::new(self.seed, tile_index);
+
+        // grid side of the stratification; 1 degenerates to plain jittering
+        let grid = if self.stratified {
+            (self.samples as f64).sqrt().round().max(1.0) as usize
+        } else {
+            1
+        };
+        let samples_per_pixel = if self.stratified { grid * grid } else { self.samples };
+
+        for dy in 0..tile.h {
+            for dx in 0..tile.w {
+                let (x, y) = (tile.x + dx, tile.y + dy);
+
+                // an independent per-pixel permutation of the lens strata, so
+                // a pixel's lens sample for stratum `s` isn't locked to the
+                // same stratum as its pixel-position sample: without this,
+                // out-of-focus bokeh shows a structured artifact correlated
+                // with sub-pixel position. Only allocated in the stratified
+                // branch, since it's unused (and would be a needless
+                // per-pixel allocation) otherwise.
+                let lens_strata: Vec<usize> = if self.stratified {
+                    let mut lens_strata: Vec<usize> = (0..samples_per_pixel).collect();
+                    rng.shuffle(&mut lens_strata);
+                    lens_strata
+                } else {
+                    Vec::new()
+                };
+
+                for s in 0..samples_per_pixel {
+                    let (px, py, lens_u, lens_v) = if self.stratified {
+                        let (i, j) = (s % grid, s / grid);
+                        let (li, lj) = (lens_strata[s] % grid, lens_strata[s] / grid);
+                        let grid = grid as f64;
+                        let px = x as f64 + (i as f64 + rng.range(0.0..1.0)) / grid;
+                        let py = y as f64 + (j as f64 + rng.range(0.0..1.0)) / grid;
+                        let lens_u = (li as f64 + rng.range(0.0..1.0)) / grid;
+                        let lens_v = (lj as f64 + rng.range(0.0..1.0)) / grid;
+                        (px, py, lens_u, lens_v)
+                    } else {
+                        let px = x as f64 + rng.range(0.0..1.0);
+                        let py = y as f64 + rng.range(0.0..1.0);
+                        (px, py, rng.range(0.0..1.0), rng.range(0.0..1.0))
+                    };
+                    let time_jitter = rng.normal();
+                    let color = self.sample(px, py, width, height, lens_u, lens_v, time_jitter);
+                    film.add_sample(px - x0 as f64, py - y0 as f64, &color);
+                }
+            }
+        }
+        (x0, y0, film)
+    }
+
     /// # Errors
     /// When open or save to file failed
     #[allow(clippy::needless_pass_by_value)] // Directly used public API, add & will make it harder to use
@@ -135,14 +418,49 @@ impl<'c, 'w> TakePhotoSettings<'c, 'w> {
             clippy::cast_precision_loss,
             clippy::cast_possible_truncation
         )]
-        Painter::new(
-            (self.picture_height as f64 * self.camera.aspect_ratio).round() as usize,
-            self.picture_height,
-        )
-        .set_samples(self.samples)
-        .draw(&path, |u, v| -> Vec3 {
-            let ray = self.camera.ray(u, v);
-            Self::ray_color(&ray, self.world, self.depth).into()
+        let width = (self.picture_height as f64 * self.camera.aspect_ratio).round() as usize;
+        let height = self.picture_height;
+
+        let tiles = Tile::grid(width, height);
+        let total = tiles.len();
+        let next_tile = AtomicUsize::new(0);
+        let (tx, rx) = mpsc::channel();
+
+        let threads = if self.threads == 0 {
+            thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+        } else {
+            self.threads
+        };
+
+        thread::scope(|scope| {
+            for _ in 0..threads.min(total).max(1) {
+                let tx = tx.clone();
+                let tiles = &tiles;
+                let next_tile = &next_tile;
+                scope.spawn(move || loop {
+                    let index = next_tile.fetch_add(1, Ordering::Relaxed);
+                    let Some(&tile) = tiles.get(index) else {
+                        break;
+                    };
+                    let rendered = self.render_tile(tile, index, width, height);
+                    if tx.send(rendered).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(tx);
+
+            let mut film = Film::new(width, height, self.filter);
+            let mut done = 0;
+            for (x0, y0, tile_film) in rx {
+                film.accumulate(x0, y0, &tile_film);
+                done += 1;
+                if let Some(progress) = self.progress {
+                    progress(done, total);
+                }
+            }
+
+            Painter::new(width, height).draw_buffer(&path, &film.resolve(true))
         })
     }
 }
@@ -247,3 +565,195 @@ impl CameraBuilder {
         )
     }
 }
+
+fn lerp(a: &Vec3, b: &Vec3, t: f64) -> Vec3 {
+    a + (b - a) * t
+}
+
+/// Interpolation curve used to ease between keyframes instead of moving at a
+/// constant rate.
+#[derive(Debug, Clone, Copy)]
+pub enum Ease {
+    Linear,
+    Smoothstep,
+}
+
+impl Ease {
+    fn apply(self, t: f64) -> f64 {
+        match self {
+            Self::Linear => t,
+            Self::Smoothstep => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Keyframe {
+    time: f64,
+    look_from: Point3,
+    look_at: Point3,
+    vup: Vec3,
+    fov: f64,
+    aperture: f64,
+    focus_distance: f64,
+}
+
+impl Keyframe {
+    fn from_builder(time: f64, builder: &CameraBuilder) -> Self {
+        Self {
+            time,
+            look_from: builder.look_from.clone(),
+            look_at: builder.look_at.clone(),
+            vup: builder.vup.clone(),
+            fov: builder.fov,
+            aperture: builder.aperture,
+            focus_distance: builder.focus_distance,
+        }
+    }
+}
+
+/// A fly-through: a set of timestamped [`CameraBuilder`] keyframes that get
+/// interpolated into a [`Camera`] pose for each frame, and again for every
+/// individual sample within that frame's shutter window — so a fast camera
+/// move blurs instead of producing a sharp frame — via the same shutter-time
+/// jitter already used for scene motion blur (see [`CameraAnimation::render`]).
+/// `aspect_ratio` and `shutter_speed` are not keyframed: they're taken once
+/// from the `base` builder passed to [`CameraAnimation::new`] and held
+/// constant across the whole animation. Every [`CameraAnimation::keyframe`]
+/// call debug-asserts that its builder agrees with that base on both; in
+/// release builds a conflicting value is ignored.
+#[derive(Debug, Clone)]
+pub struct CameraAnimation {
+    keyframes: Vec<Keyframe>,
+    frames: usize,
+    ease: Ease,
+    aspect_ratio: f64,
+    shutter_speed: f64,
+}
+
+impl CameraAnimation {
+    #[must_use]
+    pub fn new(base: &CameraBuilder) -> Self {
+        Self {
+            keyframes: Vec::new(),
+            frames: 1,
+            ease: Ease::Linear,
+            aspect_ratio: base.aspect_ratio,
+            shutter_speed: base.shutter_speed,
+        }
+    }
+
+    #[must_use]
+    pub fn keyframe(mut self, time: f64, builder: CameraBuilder) -> Self {
+        debug_assert!(
+            (builder.aspect_ratio - self.aspect_ratio).abs() < f64::EPSILON,
+            "keyframe aspect_ratio ({}) must match the animation's ({}), set once via the base \
+             builder passed to CameraAnimation::new",
+            builder.aspect_ratio,
+            self.aspect_ratio
+        );
+        debug_assert!(
+            (builder.shutter_speed - self.shutter_speed).abs() < f64::EPSILON,
+            "keyframe shutter_speed ({}) must match the animation's ({}), set once via the base \
+             builder passed to CameraAnimation::new",
+            builder.shutter_speed,
+            self.shutter_speed
+        );
+        self.keyframes.push(Keyframe::from_builder(time, &builder));
+        self.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        self
+    }
+
+    #[must_use]
+    pub const fn frames(mut self, frames: usize) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    #[must_use]
+    pub const fn ease(mut self, ease: Ease) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    // the interpolated camera builder at animation time `t`, clamped to the
+    // first/last keyframe outside their time span
+    fn sample(&self, t: f64) -> CameraBuilder {
+        let single = |k: &Keyframe| {
+            CameraBuilder::default()
+                .look_from(k.look_from.clone())
+                .look_at(k.look_at.clone())
+                .vup(k.vup.clone())
+                .fov(k.fov)
+                .aspect_ratio(self.aspect_ratio)
+                .aperture(k.aperture)
+                .focus(k.focus_distance)
+                .shutter_speed(self.shutter_speed)
+        };
+        let Some(first) = self.keyframes.first() else {
+            return CameraBuilder::default();
+        };
+        if self.keyframes.len() < 2 {
+            return single(first);
+        }
+
+        let n = self.keyframes.len();
+        let segment = self
+            .keyframes
+            .windows(2)
+            .find(|w| t <= w[1].time)
+            .unwrap_or(&self.keyframes[n - 2..n]);
+        let (k0, k1) = (&segment[0], &segment[1]);
+        let span = (k1.time - k0.time).max(f64::EPSILON);
+        let local_t = self.ease.apply(((t - k0.time) / span).clamp(0.0, 1.0));
+
+        CameraBuilder::default()
+            .look_from(lerp(&k0.look_from, &k1.look_from, local_t))
+            .look_at(lerp(&k0.look_at, &k1.look_at, local_t))
+            .vup(lerp(&k0.vup, &k1.vup, local_t))
+            .fov(k0.fov + (k1.fov - k0.fov) * local_t)
+            .aspect_ratio(self.aspect_ratio)
+            .aperture(k0.aperture + (k1.aperture - k0.aperture) * local_t)
+            .focus(k0.focus_distance + (k1.focus_distance - k0.focus_distance) * local_t)
+            .shutter_speed(self.shutter_speed)
+    }
+
+    /// Renders the sequence to numbered files `{prefix}_0000.png`,
+    /// `{prefix}_0001.png`, ..., spacing frames evenly across the keyframes'
+    /// time span. `settings` configures each frame's [`TakePhotoSettings`]
+    /// (depth, samples, filter, etc.) before it is shot.
+    ///
+    /// Each sample within a frame resamples the camera pose at
+    /// `t + shutter_speed * time_jitter` — the same shutter-time jitter used
+    /// for scene motion blur — instead of holding the frame's camera fixed,
+    /// so a keyframe move that's fast relative to `shutter_speed` blurs.
+    ///
+    /// # Errors
+    /// When a frame fails to render or save.
+    pub fn render<F>(&self, world: &World, prefix: &str, settings: F) -> std::io::Result<()>
+    where
+        F: Fn(TakePhotoSettings) -> TakePhotoSettings,
+    {
+        let start = self.keyframes.first().map_or(0.0, |k| k.time);
+        let end = self.keyframes.last().map_or(0.0, |k| k.time);
+
+        for frame in 0..self.frames {
+            #[allow(clippy::cast_precision_loss)]
+            let t = if self.frames <= 1 {
+                start
+            } else {
+                start + (end - start) * frame as f64 / (self.frames - 1) as f64
+            };
+            let camera = self.sample(t).build();
+
+            let animation = self.clone();
+            let motion: Arc<dyn Fn(f64) -> Camera + Send + Sync> = Arc::new(move |time_jitter| {
+                animation.sample(t + animation.shutter_speed * time_jitter).build()
+            });
+
+            let path = format!("{prefix}_{frame:04}.png");
+            settings(camera.take_photo(world).motion(motion)).shot(Some(path))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+use crate::prelude::{Color, Vec3};
+
+/// Reconstruction kernel used to splat a sample's contribution across the
+/// pixels whose centers fall within its radius. `radius` is expressed in
+/// pixel widths.
+#[derive(Debug, Clone, Copy)]
+pub enum Filter {
+    Box,
+    Tent { radius: f64 },
+    Gaussian { radius: f64, alpha: f64 },
+    Mitchell { radius: f64 },
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self::Box
+    }
+}
+
+impl Filter {
+    #[must_use]
+    pub fn radius(&self) -> f64 {
+        match *self {
+            Self::Box => 0.5,
+            Self::Tent { radius } | Self::Gaussian { radius, .. } | Self::Mitchell { radius } => {
+                radius
+            }
+        }
+    }
+
+    // weight of a sample `(dx, dy)` pixel-widths away from a pixel's center
+    #[must_use]
+    pub fn weight(&self, dx: f64, dy: f64) -> f64 {
+        match *self {
+            Self::Box => 1.0,
+            Self::Tent { radius } => (radius - dx.abs()).max(0.0) * (radius - dy.abs()).max(0.0),
+            Self::Gaussian { radius, alpha } => {
+                let g = |d: f64| (f64::exp(-alpha * d * d) - f64::exp(-alpha * radius * radius)).max(0.0);
+                g(dx) * g(dy)
+            }
+            Self::Mitchell { radius } => Self::mitchell_1d(dx / radius) * Self::mitchell_1d(dy / radius),
+        }
+    }
+
+    // the standard B = C = 1/3 Mitchell-Netravali cubic (support [-2, 2])
+    // evaluated at `2 * x`, so `x` is the offset as a fraction of the
+    // filter radius and the weight is zero outside [-1, 1]
+    fn mitchell_1d(x: f64) -> f64 {
+        const B: f64 = 1.0 / 3.0;
+        const C: f64 = 1.0 / 3.0;
+        let x = (2.0 * x).abs();
+        if x > 2.0 {
+            0.0
+        } else if x > 1.0 {
+            ((-B - 6.0 * C) * x.powi(3) + (6.0 * B + 30.0 * C) * x.powi(2)
+                + (-12.0 * B - 48.0 * C) * x
+                + (8.0 * B + 24.0 * C))
+                / 6.0
+        } else {
+            ((12.0 - 9.0 * B - 6.0 * C) * x.powi(3) + (-18.0 + 12.0 * B + 6.0 * C) * x.powi(2)
+                + (6.0 - 2.0 * B))
+                / 6.0
+        }
+    }
+}
+
+/// A weighted sample accumulator: every sample splats into all pixels within
+/// its filter's radius instead of only the pixel it was drawn for, which is
+/// what lets a non-box filter reduce aliasing at edges.
+#[derive(Debug, Clone)]
+pub struct Film {
+    width: usize,
+    height: usize,
+    filter: Filter,
+    sums: Vec<Vec3>,
+    weights: Vec<f64>,
+}
+
+impl Film {
+    #[must_use]
+    pub fn new(width: usize, height: usize, filter: Filter) -> Self {
+        Self {
+            width,
+            height,
+            filter,
+            sums: vec![Vec3::default(); width * height],
+            weights: vec![0.0; width * height],
+        }
+    }
+
+    /// Splat one sample taken at continuous position `(px, py)` (pixel `x`
+    /// spans `[x, x + 1)`, centered at `x + 0.5`) into every pixel whose
+    /// center lies within the filter radius.
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn add_sample(&mut self, px: f64, py: f64, color: &Vec3) {
+        let radius = self.filter.radius();
+        let x_lo = (px - radius).floor().max(0.0) as usize;
+        let y_lo = (py - radius).floor().max(0.0) as usize;
+        let x_hi = ((px + radius).floor() as usize).min(self.width.saturating_sub(1));
+        let y_hi = ((py + radius).floor() as usize).min(self.height.saturating_sub(1));
+
+        for y in y_lo..=y_hi {
+            for x in x_lo..=x_hi {
+                let (dx, dy) = (x as f64 + 0.5 - px, y as f64 + 0.5 - py);
+                if dx.abs() > radius || dy.abs() > radius {
+                    continue;
+                }
+                let weight = self.filter.weight(dx, dy);
+                // Mitchell's outer lobes are legitimately negative (that's
+                // what gives it edge-sharpening/ringing); only a genuinely
+                // zero-weight sample is a no-op write, not a negative one.
+                if weight == 0.0 {
+                    continue;
+                }
+                let i = y * self.width + x;
+                self.sums[i] += color * weight;
+                self.weights[i] += weight;
+            }
+        }
+    }
+
+    /// Add another film's samples into this one, offset by `(x0, y0)`. Used
+    /// to merge a worker's tile (rendered with a halo so splats can cross
+    /// tile boundaries) back into the full-image film.
+    pub fn accumulate(&mut self, x0: usize, y0: usize, other: &Self) {
+        for y in 0..other.height {
+            for x in 0..other.width {
+                let (ox, oy) = (x0 + x, y0 + y);
+                if ox >= self.width || oy >= self.height {
+                    continue;
+                }
+                let (si, oi) = (oy * self.width + ox, y * other.width + x);
+                self.sums[si] += &other.sums[oi];
+                self.weights[si] += other.weights[oi];
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn resolve(&self, gamma: bool) -> Vec<Color> {
+        self.sums
+            .iter()
+            .zip(&self.weights)
+            .map(|(sum, &weight)| {
+                if weight <= 0.0 {
+                    Color::default()
+                } else {
+                    (sum / weight).into_color(1, gamma)
+                }
+            })
+            .collect()
+    }
+}
@@ -81,6 +81,27 @@ impl Vec3 {
         }
     }
 
+    /// Samples a direction in the local frame whose `z` axis is "up",
+    /// weighted by `cos θ` rather than uniformly over the hemisphere. Pairs
+    /// with [`Onb`] to map the result onto a surface normal, and with
+    /// [`Vec3::cosine_pdf`] to get the matching pdf for importance sampling.
+    #[must_use]
+    pub fn random_cosine_direction() -> Self {
+        let r1: f64 = Random::range(0.0..1.0);
+        let r2: f64 = Random::range(0.0..1.0);
+        let phi = 2.0 * PI * r1;
+        let sqrt_r2 = r2.sqrt();
+        Self::new(phi.cos() * sqrt_r2, phi.sin() * sqrt_r2, (1.0 - r2).sqrt())
+    }
+
+    /// pdf of a direction sampled by [`Vec3::random_cosine_direction`],
+    /// i.e. `cos θ / π`, given `cos_theta` between that direction and the
+    /// surface normal it was mapped around.
+    #[must_use]
+    pub fn cosine_pdf(cos_theta: f64) -> f64 {
+        (cos_theta / PI).max(0.0)
+    }
+
     #[must_use]
     pub fn random_unit_disk() -> Self {
         loop {
@@ -91,6 +112,24 @@ impl Vec3 {
         }
     }
 
+    /// Maps a sample `(u, v)` in `[0, 1)^2` onto the unit disk via Shirley's
+    /// concentric mapping. Unlike [`Vec3::random_unit_disk`]'s rejection
+    /// sampling, this is a bijection, so a stratified `(u, v)` grid stays
+    /// evenly distributed once mapped onto the disk.
+    #[must_use]
+    pub fn unit_disk_from_square(u: f64, v: f64) -> Self {
+        let (a, b) = (2.0 * u - 1.0, 2.0 * v - 1.0);
+        if a == 0.0 && b == 0.0 {
+            return Self::default();
+        }
+        let (r, theta) = if a.abs() > b.abs() {
+            (a, PI / 4.0 * (b / a))
+        } else {
+            (b, PI / 2.0 - PI / 4.0 * (a / b))
+        };
+        Self::new(r * theta.cos(), r * theta.sin(), 0.0)
+    }
+
     #[must_use]
     pub fn length_squared(&self) -> f64 {
         self.z
@@ -139,6 +178,37 @@ impl Vec3 {
     }
 }
 
+/// Orthonormal basis around a surface normal, used to map a direction
+/// sampled in a local "z-up" frame (e.g. [`Vec3::random_cosine_direction`])
+/// onto the hemisphere above that normal.
+#[derive(Debug, Clone)]
+pub struct Onb {
+    u: Vec3,
+    v: Vec3,
+    w: Vec3,
+}
+
+impl Onb {
+    #[must_use]
+    pub fn from_normal(n: &Vec3) -> Self {
+        let w = n.unit();
+        let a = if w.x.abs() > 0.9 {
+            Vec3::new(0.0, 1.0, 0.0)
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let v = w.cross(&a).unit();
+        let u = w.cross(&v);
+        Self { u, v, w }
+    }
+
+    /// Maps a local-frame direction `(x, y, z)` into world space.
+    #[must_use]
+    pub fn local(&self, direction: &Vec3) -> Vec3 {
+        direction.x * &self.u + direction.y * &self.v + direction.z * &self.w
+    }
+}
+
 impl Display for Vec3 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("{} {} {}", self.x, self.y, self.z))
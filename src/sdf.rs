@@ -0,0 +1,170 @@
+use {
+    crate::{
+        geometry::{Geometry, Hit},
+        material::Material,
+        prelude::*,
+    },
+    std::ops::Range,
+};
+
+const MAX_STEPS: usize = 256;
+const EPSILON: f64 = 1e-4;
+const NORMAL_H: f64 = 1e-4;
+
+/// A signed distance field: negative inside the surface, positive outside,
+/// zero on the boundary. `distance` only needs to be a lower bound on the
+/// true distance (as with the smooth union below, which only ever lowers
+/// the estimate below the hard `min`): it must never *overestimate*, or the
+/// marcher in [`march`] can step past a surface that's actually closer than
+/// the estimate says. Underestimating just costs extra steps, which is safe.
+pub trait Sdf: Sync {
+    fn distance(&self, p: &Point3) -> f64;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sphere {
+    pub radius: f64,
+}
+
+impl Sdf for Sphere {
+    fn distance(&self, p: &Point3) -> f64 {
+        p.length() - self.radius
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BoxSdf {
+    pub half_extents: Vec3,
+}
+
+impl Sdf for BoxSdf {
+    fn distance(&self, p: &Point3) -> f64 {
+        let q = Vec3::new(
+            p.x.abs() - self.half_extents.x,
+            p.y.abs() - self.half_extents.y,
+            p.z.abs() - self.half_extents.z,
+        );
+        let outside = Vec3::new(q.x.max(0.0), q.y.max(0.0), q.z.max(0.0)).length();
+        let inside = q.x.max(q.y).max(q.z).min(0.0);
+        outside + inside
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Torus {
+    pub major_radius: f64,
+    pub minor_radius: f64,
+}
+
+impl Sdf for Torus {
+    fn distance(&self, p: &Point3) -> f64 {
+        let ring = (p.x * p.x + p.z * p.z).sqrt() - self.major_radius;
+        ring.hypot(p.y) - self.minor_radius
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub offset: f64,
+}
+
+impl Sdf for Plane {
+    fn distance(&self, p: &Point3) -> f64 {
+        p.dot(&self.normal) - self.offset
+    }
+}
+
+/// `min(a, b)`: the region covered by either `a` or `b`.
+pub struct Union<A, B>(pub A, pub B);
+
+impl<A: Sdf, B: Sdf> Sdf for Union<A, B> {
+    fn distance(&self, p: &Point3) -> f64 {
+        self.0.distance(p).min(self.1.distance(p))
+    }
+}
+
+/// `max(a, b)`: the region covered by both `a` and `b`.
+pub struct Intersection<A, B>(pub A, pub B);
+
+impl<A: Sdf, B: Sdf> Sdf for Intersection<A, B> {
+    fn distance(&self, p: &Point3) -> f64 {
+        self.0.distance(p).max(self.1.distance(p))
+    }
+}
+
+/// `max(a, -b)`: `a` with the region covered by `b` carved out of it.
+pub struct Subtraction<A, B>(pub A, pub B);
+
+impl<A: Sdf, B: Sdf> Sdf for Subtraction<A, B> {
+    fn distance(&self, p: &Point3) -> f64 {
+        self.0.distance(p).max(-self.1.distance(p))
+    }
+}
+
+/// Polynomial smooth union, blending `a` and `b` together over a region of
+/// size `k` instead of taking a hard `min`.
+pub struct SmoothUnion<A, B> {
+    pub a: A,
+    pub b: B,
+    pub k: f64,
+}
+
+impl<A: Sdf, B: Sdf> Sdf for SmoothUnion<A, B> {
+    fn distance(&self, p: &Point3) -> f64 {
+        let (da, db) = (self.a.distance(p), self.b.distance(p));
+        let h = (0.5 + 0.5 * (db - da) / self.k).clamp(0.0, 1.0);
+        db * (1.0 - h) + da * h - self.k * h * (1.0 - h)
+    }
+}
+
+/// Sphere-traces `ray` through `sdf`, stepping `t` by the field's distance
+/// estimate until it is within [`EPSILON`] of the surface (a hit) or `t`
+/// exceeds `t_range.end` (a miss), capped at [`MAX_STEPS`] iterations.
+/// A negative distance (a point inside the surface) steps `t` backwards;
+/// `t` is not checked against `t_range.start`.
+#[must_use]
+pub fn march(sdf: &dyn Sdf, ray: &Ray, t_range: &Range<f64>) -> Option<(f64, Point3)> {
+    let mut t = t_range.start;
+    for _ in 0..MAX_STEPS {
+        let p = &ray.origin + t * &ray.direction;
+        let d = sdf.distance(&p);
+        if d.abs() < EPSILON {
+            return Some((t, p));
+        }
+        t += d;
+        if t > t_range.end {
+            return None;
+        }
+    }
+    None
+}
+
+/// Surface normal at `p` via central differences of the distance field.
+#[must_use]
+pub fn normal(sdf: &dyn Sdf, p: &Point3) -> Vec3 {
+    let axis = |i: usize| {
+        let mut plus = p.clone();
+        plus[i] += NORMAL_H;
+        let mut minus = p.clone();
+        minus[i] -= NORMAL_H;
+        sdf.distance(&plus) - sdf.distance(&minus)
+    };
+    Vec3::new(axis(0), axis(1), axis(2)).unit()
+}
+
+/// Bridges an [`Sdf`] into the same [`Geometry`]/material pipeline used by
+/// the analytic primitives, so SDF and triangle/sphere geometry can share a
+/// `World` and lighting.
+pub struct SdfObject<S, M> {
+    pub sdf: S,
+    pub material: M,
+}
+
+impl<S: Sdf, M: Material> Geometry for SdfObject<S, M> {
+    fn hit(&self, ray: &Ray, t_range: Range<f64>) -> Option<Hit> {
+        let (t, point) = march(&self.sdf, ray, &t_range)?;
+        let outward_normal = normal(&self.sdf, &point);
+        Some(Hit::new(t, point, &outward_normal, ray, &self.material))
+    }
+}